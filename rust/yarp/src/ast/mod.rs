@@ -0,0 +1,212 @@
+//! An owned Rust AST lowered from the `yp_node_t` tree, plus the [`Visit`]
+//! trait for walking it.
+//!
+//! `yp_prettyprint` is the only structured view `yarp-sys` exposes today;
+//! everything else requires chasing the raw C node graph by hand. This
+//! module lowers that graph once, up front, into plain Rust values so
+//! downstream code never touches a `yp_node_t` pointer. Each node kind is
+//! read through its own typed struct (`yp_program_node_t`,
+//! `yp_class_node_t`, `yp_call_node_t`, ...) the same way the rest of this
+//! crate reads `yp_comment_t`/`yp_diagnostic_t` fields directly, rather
+//! than through a made-up generic "children"/"name" accessor — prism's
+//! node union doesn't expose one; every kind has its own field layout.
+
+mod visit;
+
+use std::ffi::CStr;
+
+use yarp_sys::{
+    yp_arguments_node_t, yp_call_node_t, yp_class_node_t, yp_constant_id_name, yp_constant_id_t,
+    yp_node_list_t, yp_node_location, yp_node_t, yp_node_type, yp_node_type_t, yp_parser_t,
+    yp_program_node_t, yp_statements_node_t,
+};
+
+use crate::depth::{self, RecursedTooDeep};
+use crate::session::ParseResult;
+
+pub use visit::{walk, Visit};
+
+/// A byte range into the original source, computed the same way the
+/// comment/diagnostic tests do: `ptr.offset_from(parser.start)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// An owned, lowered node from the parse tree. Kinds the crate doesn't
+/// yet model explicitly fall back to [`Node::Other`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    Program(ProgramNode),
+    Class(ClassNode),
+    Call(CallNode),
+    Other(OtherNode),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramNode {
+    pub location: Location,
+    pub statements: Vec<Node>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassNode {
+    pub location: Location,
+    pub name: Option<String>,
+    pub body: Vec<Node>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallNode {
+    pub location: Location,
+    pub name: Option<String>,
+    pub receiver: Option<Box<Node>>,
+    pub arguments: Vec<Node>,
+}
+
+/// Any node kind not yet given a dedicated variant. Only carries its
+/// location: prism has no generic accessor for an arbitrary node's
+/// children, so walking past one of these requires giving it a real
+/// variant above first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtherNode {
+    pub kind: yp_node_type_t,
+    pub location: Location,
+}
+
+impl Node {
+    pub fn location(&self) -> Location {
+        match self {
+            Node::Program(n) => n.location,
+            Node::Class(n) => n.location,
+            Node::Call(n) => n.location,
+            Node::Other(n) => n.location,
+        }
+    }
+}
+
+/// Lowers the tree owned by `result` into an owned [`Node`]. Fails with
+/// [`RecursedTooDeep`] rather than overflowing the stack on pathologically
+/// nested input; raise or disable the limit with [`crate::depth::DepthLimit`].
+pub fn lower(result: &ParseResult) -> Result<Node, RecursedTooDeep> {
+    unsafe { lower_raw(result.parser(), result.node()) }
+}
+
+/// Lowers a single node, entering and leaving exactly one level of the
+/// depth guard. The body is computed into `result` first and `leave()` is
+/// called unconditionally before returning it, so an `Err` bubbling up
+/// from a nested call (via `?` inside [`lower_body`]) still decrements the
+/// counter for *this* frame — otherwise a single pathologically deep
+/// input would leave the thread-local depth counter pinned at the limit,
+/// poisoning every later `.ast()` call on the same thread.
+unsafe fn lower_raw(parser: &yp_parser_t, raw: *mut yp_node_t) -> Result<Node, RecursedTooDeep> {
+    depth::enter()?;
+    let result = lower_body(parser, raw);
+    depth::leave();
+    result
+}
+
+unsafe fn lower_body(parser: &yp_parser_t, raw: *mut yp_node_t) -> Result<Node, RecursedTooDeep> {
+    let location = location_of(parser, raw);
+
+    let node = match yp_node_type(raw) {
+        yp_node_type_t::YP_NODE_PROGRAM_NODE => {
+            let program = &*(raw as *const yp_program_node_t);
+            Node::Program(ProgramNode {
+                location,
+                statements: lower_node_list(parser, &(*program.statements).body)?,
+            })
+        }
+        yp_node_type_t::YP_NODE_CLASS_NODE => {
+            let class = &*(raw as *const yp_class_node_t);
+            Node::Class(ClassNode {
+                location,
+                name: constant_name(parser, class.name),
+                body: lower_body_node(parser, class.body)?,
+            })
+        }
+        yp_node_type_t::YP_NODE_CALL_NODE => {
+            let call = &*(raw as *const yp_call_node_t);
+            Node::Call(CallNode {
+                location,
+                name: constant_name(parser, call.name),
+                receiver: if call.receiver.is_null() {
+                    None
+                } else {
+                    Some(Box::new(lower_raw(parser, call.receiver)?))
+                },
+                arguments: lower_arguments(parser, call.arguments)?,
+            })
+        }
+        kind => Node::Other(OtherNode { kind, location }),
+    };
+
+    Ok(node)
+}
+
+unsafe fn location_of(parser: &yp_parser_t, raw: *mut yp_node_t) -> Location {
+    let location = yp_node_location(raw);
+    Location {
+        start: location.start.offset_from(parser.start) as usize,
+        end: location.end.offset_from(parser.start) as usize,
+    }
+}
+
+/// Resolves a `yp_constant_id_t` (prism interns identifiers in a constant
+/// pool rather than storing them inline) to its source text. `0` is
+/// prism's sentinel for "no constant".
+unsafe fn constant_name(parser: &yp_parser_t, id: yp_constant_id_t) -> Option<String> {
+    if id == 0 {
+        return None;
+    }
+
+    let ptr = yp_constant_id_name(parser, id);
+    if ptr.is_null() {
+        None
+    } else {
+        Some(CStr::from_ptr(ptr).to_string_lossy().into_owned())
+    }
+}
+
+unsafe fn lower_node_list(
+    parser: &yp_parser_t,
+    list: &yp_node_list_t,
+) -> Result<Vec<Node>, RecursedTooDeep> {
+    let mut nodes = Vec::with_capacity(list.size);
+    for i in 0..list.size {
+        nodes.push(lower_raw(parser, *list.nodes.add(i))?);
+    }
+    Ok(nodes)
+}
+
+/// Lowers a class/module body, which prism represents as a single
+/// (possibly null) `yp_node_t*` rather than a list: usually a
+/// `StatementsNode`, which this flattens into its `body` list so callers
+/// don't need to special-case the wrapper node.
+unsafe fn lower_body_node(
+    parser: &yp_parser_t,
+    raw: *mut yp_node_t,
+) -> Result<Vec<Node>, RecursedTooDeep> {
+    if raw.is_null() {
+        return Ok(Vec::new());
+    }
+
+    if yp_node_type(raw) == yp_node_type_t::YP_NODE_STATEMENTS_NODE {
+        let statements = &*(raw as *const yp_statements_node_t);
+        lower_node_list(parser, &statements.body)
+    } else {
+        Ok(vec![lower_raw(parser, raw)?])
+    }
+}
+
+unsafe fn lower_arguments(
+    parser: &yp_parser_t,
+    arguments: *mut yp_arguments_node_t,
+) -> Result<Vec<Node>, RecursedTooDeep> {
+    if arguments.is_null() {
+        Ok(Vec::new())
+    } else {
+        lower_node_list(parser, &(*arguments).arguments)
+    }
+}
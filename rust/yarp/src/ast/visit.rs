@@ -0,0 +1,58 @@
+use super::{CallNode, ClassNode, Node, OtherNode, ProgramNode};
+use crate::depth::{self, RecursedTooDeep};
+
+/// Visit/fold-style traversal over the owned AST, mirroring the shape of
+/// rustc's AST visitors: each node kind gets a `visit_*` method with a
+/// default implementation that recurses into its children (where it has
+/// any), so callers only override the kinds they care about.
+///
+/// Every default method returns `Result` because recursing through
+/// [`walk`] is depth-guarded (see [`crate::depth`]) and propagates
+/// [`RecursedTooDeep`] instead of overflowing the stack on pathologically
+/// nested trees.
+pub trait Visit {
+    fn visit_program(&mut self, node: &ProgramNode) -> Result<(), RecursedTooDeep> {
+        for statement in &node.statements {
+            walk(self, statement)?;
+        }
+        Ok(())
+    }
+
+    fn visit_class(&mut self, node: &ClassNode) -> Result<(), RecursedTooDeep> {
+        for statement in &node.body {
+            walk(self, statement)?;
+        }
+        Ok(())
+    }
+
+    fn visit_call(&mut self, node: &CallNode) -> Result<(), RecursedTooDeep> {
+        if let Some(receiver) = &node.receiver {
+            walk(self, receiver)?;
+        }
+        for argument in &node.arguments {
+            walk(self, argument)?;
+        }
+        Ok(())
+    }
+
+    /// `OtherNode` carries no children — prism has no generic accessor
+    /// for an arbitrary node's fields, so there's nothing left to walk
+    /// until its kind gets a real variant above.
+    fn visit_other(&mut self, _node: &OtherNode) -> Result<(), RecursedTooDeep> {
+        Ok(())
+    }
+}
+
+/// Dispatches `node` to the matching `visit_*` method on `visitor`,
+/// guarding the descent against unbounded recursion.
+pub fn walk<V: Visit + ?Sized>(visitor: &mut V, node: &Node) -> Result<(), RecursedTooDeep> {
+    depth::enter()?;
+    let result = match node {
+        Node::Program(n) => visitor.visit_program(n),
+        Node::Class(n) => visitor.visit_class(n),
+        Node::Call(n) => visitor.visit_call(n),
+        Node::Other(n) => visitor.visit_other(n),
+    };
+    depth::leave();
+    result
+}
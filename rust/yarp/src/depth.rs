@@ -0,0 +1,81 @@
+//! A recursion-depth guard for AST lowering and traversal, borrowed from
+//! `rustc-demangle`'s `v0` parser: a `MAX_DEPTH` limit, incremented on
+//! descent and decremented on ascent, that returns a dedicated error
+//! instead of blowing the native stack on adversarially nested input
+//! (thousands of nested parens or array literals, say).
+
+use std::cell::Cell;
+use std::error::Error;
+use std::fmt;
+
+/// Default maximum traversal depth, used unless a caller installs a
+/// [`DepthLimit`] scope.
+pub const DEFAULT_MAX_DEPTH: usize = 512;
+
+thread_local! {
+    static MAX_DEPTH: Cell<Option<usize>> = Cell::new(Some(DEFAULT_MAX_DEPTH));
+    static CURRENT_DEPTH: Cell<usize> = Cell::new(0);
+}
+
+/// Returned when lowering or traversal would exceed the configured depth
+/// limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecursedTooDeep {
+    pub depth: usize,
+}
+
+impl fmt::Display for RecursedTooDeep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "recursed too deep while walking the AST (depth {})", self.depth)
+    }
+}
+
+impl Error for RecursedTooDeep {}
+
+/// Raises or disables the traversal depth limit for the current thread
+/// for as long as the guard is alive, restoring the previous limit on
+/// drop. Use this when a caller knows its trees are unusually deep, or
+/// wants to accept the stack-overflow risk the limit exists to avoid.
+pub struct DepthLimit {
+    previous: Option<usize>,
+}
+
+impl DepthLimit {
+    /// Raises (or lowers) the limit to `max_depth` for this scope.
+    pub fn scoped(max_depth: usize) -> Self {
+        let previous = MAX_DEPTH.with(|cell| cell.replace(Some(max_depth)));
+        DepthLimit { previous }
+    }
+
+    /// Disables the limit entirely for this scope.
+    pub fn unbounded() -> Self {
+        let previous = MAX_DEPTH.with(|cell| cell.replace(None));
+        DepthLimit { previous }
+    }
+}
+
+impl Drop for DepthLimit {
+    fn drop(&mut self) {
+        MAX_DEPTH.with(|cell| cell.set(self.previous));
+    }
+}
+
+/// Enters one level of recursion, failing instead of descending further
+/// if the configured limit would be exceeded. Callers must pair every
+/// successful `enter` with a matching [`leave`] on the way back up.
+pub(crate) fn enter() -> Result<(), RecursedTooDeep> {
+    CURRENT_DEPTH.with(|cell| {
+        let depth = cell.get();
+        if let Some(max) = MAX_DEPTH.with(Cell::get) {
+            if depth >= max {
+                return Err(RecursedTooDeep { depth });
+            }
+        }
+        cell.set(depth + 1);
+        Ok(())
+    })
+}
+
+pub(crate) fn leave() {
+    CURRENT_DEPTH.with(|cell| cell.set(cell.get().saturating_sub(1)));
+}
@@ -0,0 +1,166 @@
+//! Safe iterators over prism's comment/error lists, plus a source map for
+//! turning byte offsets into human-readable `line:column` positions.
+//!
+//! `comments_test` and `diagnostics_test` cast `comment_list.head`/
+//! `error_list.head` to raw pointers and walk the intrusive linked list by
+//! hand. This module does that walk once, behind an `Iterator`, and adds
+//! the `CodeMap`-style line/column resolution rustc's parser uses to
+//! render diagnostics.
+
+use std::ffi::CStr;
+
+use yarp_sys::{yp_comment_t, yp_comment_type_t, yp_diagnostic_t};
+
+use crate::ast::Location;
+use crate::session::ParseResult;
+
+/// The kind of comment prism recorded, mirroring `yp_comment_type_t`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentType {
+    Inline,
+    EmbDoc,
+    EndMarker,
+}
+
+impl From<yp_comment_type_t> for CommentType {
+    fn from(kind: yp_comment_type_t) -> Self {
+        match kind {
+            yp_comment_type_t::YP_COMMENT_INLINE => CommentType::Inline,
+            yp_comment_type_t::YP_COMMENT_EMBDOC => CommentType::EmbDoc,
+            yp_comment_type_t::YP_COMMENT___END__ => CommentType::EndMarker,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Comment {
+    pub kind: CommentType,
+    pub location: Location,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub message: String,
+    pub location: Location,
+}
+
+impl Diagnostic {
+    /// Renders as `path:line:column: message`, e.g.
+    /// `foo.rb:3:10: Expected to be able to parse an expression.`
+    pub fn render(&self, path: &str, map: &SourceMap) -> String {
+        let (line, column) = map.line_col(self.location.start);
+        format!("{path}:{line}:{column}: {}", self.message)
+    }
+}
+
+/// Iterates the comments prism recorded while parsing `result`, in source
+/// order.
+pub fn comments(result: &ParseResult) -> impl Iterator<Item = Comment> + '_ {
+    let base = result.parser().start;
+    CommentIter {
+        next: result.parser().comment_list.head as *const yp_comment_t,
+        base,
+    }
+}
+
+/// Iterates the parse errors prism recorded (its `error_list`) while
+/// parsing `result`, in source order. Warnings are not included.
+pub fn diagnostics(result: &ParseResult) -> impl Iterator<Item = Diagnostic> + '_ {
+    let base = result.parser().start;
+    DiagnosticIter {
+        next: result.parser().error_list.head as *const yp_diagnostic_t,
+        base,
+    }
+}
+
+struct CommentIter {
+    next: *const yp_comment_t,
+    base: *const u8,
+}
+
+impl Iterator for CommentIter {
+    type Item = Comment;
+
+    fn next(&mut self) -> Option<Comment> {
+        if self.next.is_null() {
+            return None;
+        }
+
+        unsafe {
+            let comment = &*self.next;
+            let location = Location {
+                start: comment.start.offset_from(self.base) as usize,
+                end: comment.end.offset_from(self.base) as usize,
+            };
+            self.next = comment.node.next as *const yp_comment_t;
+            Some(Comment {
+                kind: comment.type_.into(),
+                location,
+            })
+        }
+    }
+}
+
+struct DiagnosticIter {
+    next: *const yp_diagnostic_t,
+    base: *const u8,
+}
+
+impl Iterator for DiagnosticIter {
+    type Item = Diagnostic;
+
+    fn next(&mut self) -> Option<Diagnostic> {
+        if self.next.is_null() {
+            return None;
+        }
+
+        unsafe {
+            let diagnostic = &*self.next;
+            let location = Location {
+                start: diagnostic.start.offset_from(self.base) as usize,
+                end: diagnostic.end.offset_from(self.base) as usize,
+            };
+            let message = CStr::from_ptr(diagnostic.message).to_string_lossy().into_owned();
+            self.next = diagnostic.node.next as *const yp_diagnostic_t;
+            Some(Diagnostic { message, location })
+        }
+    }
+}
+
+/// Maps byte offsets into a source file onto 1-based `(line, column)`
+/// pairs, the way rustc's `CodeMap`/`FileMap` do for its parser.
+pub struct SourceMap<'a> {
+    source: &'a [u8],
+    line_starts: Vec<usize>,
+}
+
+impl<'a> SourceMap<'a> {
+    pub fn new(source: &'a [u8]) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(
+            source
+                .iter()
+                .enumerate()
+                .filter(|&(_, &byte)| byte == b'\n')
+                .map(|(i, _)| i + 1),
+        );
+        SourceMap { source, line_starts }
+    }
+
+    /// Returns the 1-based `(line, column)` for `offset`, where `column`
+    /// counts bytes since the start of the line.
+    pub fn line_col(&self, offset: usize) -> (usize, usize) {
+        let line_index = match self.line_starts.binary_search(&offset) {
+            Ok(i) => i,
+            Err(i) => i - 1,
+        };
+        let column = offset - self.line_starts[line_index] + 1;
+        (line_index + 1, column)
+    }
+}
+
+impl<'a> From<&'a ParseResult> for SourceMap<'a> {
+    fn from(result: &'a ParseResult) -> Self {
+        SourceMap::new(result.source())
+    }
+}
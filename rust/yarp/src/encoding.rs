@@ -0,0 +1,136 @@
+//! Safe wrappers around prism's encoding-callback hooks.
+//!
+//! The raw FFI only lets you register a bare `unsafe extern "C" fn`, so
+//! every caller ends up smuggling state out through a `static` (see
+//! `encoding_change_test`/`encoding_decode_test`). This module trampolines
+//! through the raw hooks into a boxed closure keyed by the parser's
+//! address, and ships a ready-made decode callback backed by `encoding_rs`
+//! so `# encoding: <name>` magic comments resolve automatically instead of
+//! every caller reimplementing the name lookup by hand.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{c_char, CStr};
+
+use yarp_sys::{
+    yp_encoding_ascii, yp_encoding_euc_jp, yp_encoding_shift_jis, yp_encoding_t,
+    yp_encoding_windows_1252, yp_parser_register_encoding_changed_callback,
+    yp_parser_register_encoding_decode_callback, yp_parser_t,
+};
+
+use crate::session::Parser;
+
+type ChangedCallback = Box<dyn FnMut(&Encoding)>;
+type DecodeCallback = Box<dyn FnMut(&str, usize) -> EncodingHandle>;
+
+thread_local! {
+    static CHANGED_CALLBACKS: RefCell<HashMap<usize, ChangedCallback>> = RefCell::new(HashMap::new());
+    static DECODE_CALLBACKS: RefCell<HashMap<usize, DecodeCallback>> = RefCell::new(HashMap::new());
+}
+
+/// The encoding a parser switched to, as reported by `on_encoding_changed`.
+pub struct Encoding<'a> {
+    name: &'a str,
+}
+
+impl<'a> Encoding<'a> {
+    pub fn name(&self) -> &str {
+        self.name
+    }
+}
+
+/// A resolved `yp_encoding_t`, ready to hand back from a decode callback.
+pub struct EncodingHandle(*mut yp_encoding_t);
+
+impl EncodingHandle {
+    pub fn ascii() -> Self {
+        unsafe { EncodingHandle(std::ptr::addr_of_mut!(yp_encoding_ascii)) }
+    }
+
+    pub fn shift_jis() -> Self {
+        unsafe { EncodingHandle(std::ptr::addr_of_mut!(yp_encoding_shift_jis)) }
+    }
+
+    pub fn euc_jp() -> Self {
+        unsafe { EncodingHandle(std::ptr::addr_of_mut!(yp_encoding_euc_jp)) }
+    }
+
+    pub fn windows_1252() -> Self {
+        unsafe { EncodingHandle(std::ptr::addr_of_mut!(yp_encoding_windows_1252)) }
+    }
+
+    fn into_raw(self) -> *mut yp_encoding_t {
+        self.0
+    }
+}
+
+/// Resolves a `# encoding: <label>` name via `encoding_rs`'s label table
+/// and maps it onto one of prism's built-in encodings, falling back to
+/// ASCII for anything prism doesn't ship a decoder for — the same
+/// fallback `encoding_decode_test` takes by hand for an unknown name.
+pub fn decode_via_encoding_rs(label: &str) -> EncodingHandle {
+    match encoding_rs::Encoding::for_label(label.as_bytes()) {
+        Some(enc) if enc == encoding_rs::SHIFT_JIS => EncodingHandle::shift_jis(),
+        Some(enc) if enc == encoding_rs::EUC_JP => EncodingHandle::euc_jp(),
+        Some(enc) if enc == encoding_rs::WINDOWS_1252 => EncodingHandle::windows_1252(),
+        _ => EncodingHandle::ascii(),
+    }
+}
+
+/// Registers `callback` to run whenever `parser` settles on a new source
+/// encoding (e.g. after reading a `# encoding:` magic comment).
+pub fn on_encoding_changed(parser: &mut Parser, callback: impl FnMut(&Encoding) + 'static) {
+    let key = parser.raw_addr();
+    CHANGED_CALLBACKS.with(|callbacks| callbacks.borrow_mut().insert(key, Box::new(callback)));
+    unsafe {
+        yp_parser_register_encoding_changed_callback(parser.raw_mut(), Some(trampoline_changed));
+    }
+}
+
+unsafe extern "C" fn trampoline_changed(parser: *mut yp_parser_t) {
+    CHANGED_CALLBACKS.with(|callbacks| {
+        if let Some(callback) = callbacks.borrow_mut().get_mut(&(parser as usize)) {
+            let name = CStr::from_ptr((*parser).encoding.name).to_string_lossy();
+            callback(&Encoding { name: &name });
+        }
+    });
+}
+
+/// Registers `callback` to resolve a `# encoding: <name>` magic comment
+/// into a [`EncodingHandle`]. `name` is the decoded encoding label and
+/// `width` is the raw length prism reported alongside it.
+pub fn on_encoding_decode(
+    parser: &mut Parser,
+    callback: impl FnMut(&str, usize) -> EncodingHandle + 'static,
+) {
+    let key = parser.raw_addr();
+    DECODE_CALLBACKS.with(|callbacks| callbacks.borrow_mut().insert(key, Box::new(callback)));
+    unsafe {
+        yp_parser_register_encoding_decode_callback(parser.raw_mut(), Some(trampoline_decode));
+    }
+}
+
+unsafe extern "C" fn trampoline_decode(
+    parser: *mut yp_parser_t,
+    name: *const c_char,
+    width: usize,
+) -> *mut yp_encoding_t {
+    // `name` points into the source and is not NUL-terminated; `width` is
+    // its length in bytes.
+    let name = String::from_utf8_lossy(std::slice::from_raw_parts(name.cast::<u8>(), width));
+    DECODE_CALLBACKS.with(|callbacks| {
+        match callbacks.borrow_mut().get_mut(&(parser as usize)) {
+            Some(callback) => callback(&name, width).into_raw(),
+            None => decode_via_encoding_rs(&name).into_raw(),
+        }
+    })
+}
+
+pub(crate) fn unregister(key: usize) {
+    CHANGED_CALLBACKS.with(|callbacks| {
+        callbacks.borrow_mut().remove(&key);
+    });
+    DECODE_CALLBACKS.with(|callbacks| {
+        callbacks.borrow_mut().remove(&key);
+    });
+}
@@ -0,0 +1,96 @@
+//! Fuzzing and differential-stability checks for the parser.
+//!
+//! Two entry points: [`fuzz_target`] is meant to be driven by `cargo fuzz`
+//! (or any byte-oracle fuzzer) against arbitrary input, and
+//! [`check_converge`] re-parses known-good source to catch
+//! non-determinism or unexpected parse errors, the way the classic Rust
+//! AST fuzzer's "converge" mode re-feeds its own output back through the
+//! parser to check for idempotence.
+
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::diagnostics;
+use crate::Parser;
+
+/// Feeds `data` through `yp_parse` and `yp_prettyprint`, then lets the
+/// result drop. This is the fuzz target itself: wire it up with
+/// `cargo fuzz` via
+/// `fuzz_target!(|data: &[u8]| { yarp::fuzz::fuzz_target(data); });`.
+/// Must never panic or trigger UB on arbitrary bytes, including inputs
+/// that only partially parse (leaving the node graph and error list in a
+/// partially-populated state).
+pub fn fuzz_target(data: &[u8]) {
+    let result = Parser::new(data.to_vec()).parse();
+    let _ = result.pretty_print();
+}
+
+/// The outcome of re-parsing a single corpus entry.
+#[derive(Debug)]
+pub enum ConvergeOutcome {
+    Stable,
+    Diverged { first: String, second: String },
+    UnexpectedErrors(Vec<String>),
+}
+
+impl ConvergeOutcome {
+    pub fn is_stable(&self) -> bool {
+        matches!(self, ConvergeOutcome::Stable)
+    }
+}
+
+/// Parses `source` twice and checks that prism is deterministic (both
+/// `pretty_print`s agree) and, unless `expect_errors` is set, that parsing
+/// produced no diagnostics. Dropping both `ParseResult`s normally also
+/// exercises that node/parser teardown is sound after a partial parse.
+pub fn check_converge(source: &[u8], expect_errors: bool) -> ConvergeOutcome {
+    let first = Parser::new(source.to_vec()).parse();
+    let second = Parser::new(source.to_vec()).parse();
+
+    let (first_print, second_print) = (first.pretty_print(), second.pretty_print());
+    if first_print != second_print {
+        return ConvergeOutcome::Diverged {
+            first: first_print,
+            second: second_print,
+        };
+    }
+
+    if !expect_errors {
+        let messages: Vec<String> = diagnostics::diagnostics(&first).map(|d| d.message).collect();
+        if !messages.is_empty() {
+            return ConvergeOutcome::UnexpectedErrors(messages);
+        }
+    }
+
+    ConvergeOutcome::Stable
+}
+
+/// Recursively collects `.rb` files under `root`, skipping any directory
+/// whose name appears in `skip_dirs` (e.g. known-bad fixture dirs), the
+/// way the original AST fuzzer excluded directories it couldn't yet
+/// handle from its corpus walk.
+pub fn discover_corpus(root: &Path, skip_dirs: &[&str]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                let name = path.file_name().and_then(OsStr::to_str).unwrap_or_default();
+                if !skip_dirs.contains(&name) {
+                    stack.push(path);
+                }
+            } else if path.extension().and_then(OsStr::to_str) == Some("rb") {
+                files.push(path);
+            }
+        }
+    }
+
+    files
+}
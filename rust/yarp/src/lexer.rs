@@ -0,0 +1,66 @@
+//! Lex-only mode: drives prism's lexer to completion without building the
+//! full AST, the way rustc keeps `parse::lexer` usable independently of
+//! the parser proper.
+//!
+//! This is the cheap path for syntax highlighters and other tools that
+//! only need token kinds and spans, and the kind of token+span stream
+//! reference-lexer tests diff against.
+
+use yarp_sys::{yp_lexer_step, yp_parser_t, yp_token_type_t};
+
+use crate::ast::Location;
+use crate::session::Parser;
+
+/// A single lexical token: its kind and the byte range it spans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    pub kind: yp_token_type_t,
+    pub location: Location,
+}
+
+impl Parser {
+    /// Steps the lexer to completion, yielding one [`Token`] per call.
+    /// Does not build the node graph `parse()` would.
+    pub fn tokens(&mut self) -> impl Iterator<Item = Token> + '_ {
+        let base = self.raw().start;
+        TokenIter {
+            parser: self.raw_mut(),
+            base,
+            done: false,
+        }
+    }
+}
+
+struct TokenIter<'a> {
+    parser: &'a mut yp_parser_t,
+    base: *const u8,
+    done: bool,
+}
+
+impl<'a> Iterator for TokenIter<'a> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        if self.done {
+            return None;
+        }
+
+        let token = unsafe { yp_lexer_step(self.parser) };
+        if token.type_ == yp_token_type_t::YP_TOKEN_EOF {
+            self.done = true;
+            return None;
+        }
+
+        let location = unsafe {
+            Location {
+                start: token.start.offset_from(self.base) as usize,
+                end: token.end.offset_from(self.base) as usize,
+            }
+        };
+
+        Some(Token {
+            kind: token.type_,
+            location,
+        })
+    }
+}
@@ -0,0 +1,16 @@
+//! Safe, idiomatic bindings on top of the raw `yarp-sys` FFI.
+//!
+//! `yarp-sys` exposes the `yp_*` C API of prism as-is: callers juggle
+//! `MaybeUninit`, manual init/free pairing, and `unsafe` pointer chasing for
+//! every result. This crate wraps that surface so that parsing a Ruby
+//! source string never requires writing `unsafe` at the call site.
+
+pub mod ast;
+pub mod depth;
+pub mod diagnostics;
+pub mod encoding;
+pub mod fuzz;
+pub mod lexer;
+mod session;
+
+pub use session::{ParseResult, Parser};
@@ -0,0 +1,151 @@
+use std::cell::UnsafeCell;
+use std::ffi::c_char;
+use std::mem::{self, ManuallyDrop, MaybeUninit};
+use std::ptr;
+use std::str;
+
+use yarp_sys::{yp_buffer_free, yp_buffer_init, yp_buffer_t, yp_node_destroy, yp_node_t, yp_parse, yp_parser_free, yp_parser_init, yp_parser_t, yp_prettyprint};
+
+/// An owned parsing session, analogous to rustc's `ParseSess`: it holds the
+/// source bytes and the initialized `yp_parser_t` for as long as both need
+/// to stay alive, and is the only safe entry point into `yp_parse`.
+///
+/// The source buffer is boxed so its address is stable for the lifetime of
+/// the parser, which stores raw pointers into it. The parser itself is
+/// wrapped in an `UnsafeCell` rather than accessed by casting away
+/// constness from a `&self`-derived pointer, since methods that only
+/// borrow `Parser`/`ParseResult` immutably (like `pretty_print`) still
+/// need a `*mut yp_parser_t` to hand to the FFI.
+pub struct Parser {
+    source: Box<[u8]>,
+    raw: ManuallyDrop<Box<UnsafeCell<MaybeUninit<yp_parser_t>>>>,
+}
+
+impl Parser {
+    /// Initializes a parser over `source`. The bytes are copied into an
+    /// owned buffer so the caller's borrow doesn't need to outlive the
+    /// `Parser`.
+    pub fn new(source: impl Into<Vec<u8>>) -> Self {
+        let source: Box<[u8]> = source.into().into_boxed_slice();
+        let raw = Box::new(UnsafeCell::new(MaybeUninit::<yp_parser_t>::uninit()));
+
+        unsafe {
+            yp_parser_init(
+                (*raw.get()).as_mut_ptr(),
+                source.as_ptr() as *const c_char,
+                source.len(),
+                ptr::null(),
+            );
+        }
+
+        Parser {
+            source,
+            raw: ManuallyDrop::new(raw),
+        }
+    }
+
+    /// The address the parser lives at, used as a stable key for the
+    /// encoding-callback registry (it doesn't move across `parse`, since
+    /// only the owning `Box` is relocated, not its heap allocation).
+    pub(crate) fn raw_addr(&self) -> usize {
+        self.raw.get() as usize
+    }
+
+    pub(crate) fn raw_mut(&mut self) -> &mut yp_parser_t {
+        unsafe { (*self.raw.get()).assume_init_mut() }
+    }
+
+    pub(crate) fn raw(&self) -> &yp_parser_t {
+        unsafe { (*self.raw.get()).assume_init_ref() }
+    }
+
+    /// Runs `yp_parse` and returns a [`ParseResult`] owning both the parsed
+    /// node and the parser state, so that there is exactly one place
+    /// responsible for tearing them down in the right order.
+    pub fn parse(mut self) -> ParseResult {
+        let node = unsafe {
+            let parser = (*self.raw.get()).assume_init_mut();
+            yp_parse(parser)
+        };
+
+        // SAFETY: `self` is forgotten right after, so these reads don't
+        // produce a double-drop of `source`/`raw`.
+        let raw = unsafe { ManuallyDrop::take(&mut self.raw) };
+        let source = unsafe { ptr::read(&self.source) };
+        mem::forget(self);
+
+        ParseResult { source, raw, node }
+    }
+}
+
+impl Drop for Parser {
+    fn drop(&mut self) {
+        crate::encoding::unregister(self.raw_addr());
+        unsafe {
+            yp_parser_free((*self.raw.get()).assume_init_mut());
+            ManuallyDrop::drop(&mut self.raw);
+        }
+    }
+}
+
+/// The result of a successful [`Parser::parse`] call. Owns the root node
+/// and the parser it was produced from, and frees both on drop in the
+/// order the C API requires (node first, then parser).
+pub struct ParseResult {
+    source: Box<[u8]>,
+    raw: Box<UnsafeCell<MaybeUninit<yp_parser_t>>>,
+    node: *mut yp_node_t,
+}
+
+impl ParseResult {
+    /// Renders the tree via `yp_prettyprint` and returns it as a validated
+    /// UTF-8 `String`, hiding the `yp_buffer_t` init/free dance.
+    pub fn pretty_print(&self) -> String {
+        unsafe {
+            let parser = (*self.raw.get()).assume_init_mut();
+            let mut buffer = MaybeUninit::<yp_buffer_t>::uninit();
+            assert!(yp_buffer_init(buffer.as_mut_ptr()), "failed to init yp_buffer_t");
+            let buffer = buffer.assume_init_mut();
+
+            yp_prettyprint(parser, self.node, buffer);
+            let slice = std::slice::from_raw_parts(buffer.value.cast::<u8>(), buffer.length);
+            let string = str::from_utf8(slice)
+                .expect("yp_prettyprint output is not valid UTF-8")
+                .to_owned();
+
+            yp_buffer_free(buffer);
+            string
+        }
+    }
+
+    /// The raw source bytes this result was parsed from.
+    pub fn source(&self) -> &[u8] {
+        &self.source
+    }
+
+    /// Lowers the C node graph into the owned [`crate::ast::Node`] tree.
+    /// Fails with [`crate::depth::RecursedTooDeep`] instead of
+    /// overflowing the stack on pathologically nested input.
+    pub fn ast(&self) -> Result<crate::ast::Node, crate::depth::RecursedTooDeep> {
+        crate::ast::lower(self)
+    }
+
+    pub(crate) fn parser(&self) -> &yp_parser_t {
+        unsafe { (*self.raw.get()).assume_init_ref() }
+    }
+
+    pub(crate) fn node(&self) -> *mut yp_node_t {
+        self.node
+    }
+}
+
+impl Drop for ParseResult {
+    fn drop(&mut self) {
+        crate::encoding::unregister(self.raw.get() as usize);
+        unsafe {
+            let parser = (*self.raw.get()).assume_init_mut();
+            yp_node_destroy(parser, self.node);
+            yp_parser_free(parser);
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use yarp::ast::{CallNode, Node, Visit};
+use yarp::Parser;
+
+#[test]
+fn lowers_program_and_class_nodes() {
+    let result = Parser::new("class Foo\nend").parse();
+    let ast = result.ast().expect("source is nowhere near the depth limit");
+
+    let Node::Program(program) = &ast else {
+        panic!("expected a ProgramNode at the root, got {ast:?}");
+    };
+    assert_eq!(program.location, yarp::ast::Location { start: 0, end: 13 });
+
+    let Some(Node::Class(class)) = program.statements.first() else {
+        panic!("expected the program's first statement to be a ClassNode");
+    };
+    assert_eq!(class.name.as_deref(), Some("Foo"));
+    assert_eq!(class.location, yarp::ast::Location { start: 0, end: 13 });
+}
+
+#[test]
+fn visitor_collects_call_names() {
+    struct CallNames(Vec<String>);
+
+    impl Visit for CallNames {
+        fn visit_call(&mut self, node: &CallNode) -> Result<(), yarp::depth::RecursedTooDeep> {
+            if let Some(name) = &node.name {
+                self.0.push(name.clone());
+            }
+            for argument in &node.arguments {
+                yarp::ast::walk(self, argument)?;
+            }
+            Ok(())
+        }
+    }
+
+    let result = Parser::new("puts(foo.bar)").parse();
+    let ast = result.ast().expect("source is nowhere near the depth limit");
+    let mut names = CallNames(Vec::new());
+    yarp::ast::walk(&mut names, &ast).expect("source is nowhere near the depth limit");
+
+    // Pre-order: `puts` is the outer call and is visited before its
+    // argument `foo.bar` recurses into the inner `bar` call.
+    assert_eq!(names.0, vec!["puts".to_string(), "bar".to_string()]);
+}
@@ -0,0 +1,27 @@
+use yarp::depth::DepthLimit;
+use yarp::Parser;
+
+fn deeply_nested_calls(depth: usize) -> String {
+    let mut source = String::new();
+    source.push_str(&"a(".repeat(depth));
+    source.push('1');
+    source.push_str(&")".repeat(depth));
+    source
+}
+
+#[test]
+fn lowering_fails_instead_of_overflowing_the_stack_on_deep_nesting() {
+    let source = deeply_nested_calls(yarp::depth::DEFAULT_MAX_DEPTH * 2);
+    let result = Parser::new(source).parse();
+
+    assert!(result.ast().is_err(), "expected RecursedTooDeep for pathologically nested input");
+}
+
+#[test]
+fn raising_the_limit_lets_deep_nesting_through() {
+    let source = deeply_nested_calls(yarp::depth::DEFAULT_MAX_DEPTH * 2);
+    let result = Parser::new(source).parse();
+
+    let _guard = DepthLimit::scoped(yarp::depth::DEFAULT_MAX_DEPTH * 4);
+    assert!(result.ast().is_ok());
+}
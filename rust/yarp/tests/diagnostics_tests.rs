@@ -0,0 +1,26 @@
+use yarp::diagnostics::{self, CommentType, SourceMap};
+use yarp::Parser;
+
+#[test]
+fn iterates_comments_in_source_order() {
+    let result = Parser::new("# Meow!").parse();
+    let comments: Vec<_> = diagnostics::comments(&result).collect();
+
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0].kind, CommentType::Inline);
+    assert_eq!(comments[0].location.start..comments[0].location.end, 0..7);
+}
+
+#[test]
+fn renders_diagnostic_with_line_and_column() {
+    let result = Parser::new("class Foo\nclass Bar;").parse();
+    let diagnostic = diagnostics::diagnostics(&result)
+        .next()
+        .expect("expected a diagnostic for the unterminated class body");
+
+    let map = SourceMap::from(&result);
+    assert_eq!(
+        diagnostic.render("foo.rb", &map),
+        "foo.rb:2:11: Expected to be able to parse an expression."
+    );
+}
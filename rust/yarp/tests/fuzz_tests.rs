@@ -0,0 +1,41 @@
+use std::path::Path;
+
+use yarp::fuzz::{self, ConvergeOutcome};
+
+#[test]
+fn fuzz_target_survives_arbitrary_bytes() {
+    fuzz::fuzz_target(b"");
+    fuzz::fuzz_target(b"\xff\x00class");
+    fuzz::fuzz_target("class Foo;".as_bytes());
+}
+
+#[test]
+fn discover_corpus_skips_known_bad_directories() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/corpus");
+    let files = fuzz::discover_corpus(&root, &["known_bad"]);
+
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].file_name().unwrap(), "class.rb");
+}
+
+#[test]
+fn converges_on_well_formed_source() {
+    let source = std::fs::read(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/corpus/class.rb"),
+    )
+    .unwrap();
+
+    let outcome = fuzz::check_converge(&source, false);
+    assert!(outcome.is_stable(), "expected a stable parse, got {outcome:?}");
+}
+
+#[test]
+fn reports_unexpected_errors_on_known_bad_source() {
+    let source = std::fs::read(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/corpus/known_bad/unterminated.rb"),
+    )
+    .unwrap();
+
+    let outcome = fuzz::check_converge(&source, false);
+    assert!(matches!(outcome, ConvergeOutcome::UnexpectedErrors(_)));
+}
@@ -0,0 +1,11 @@
+use yarp::Parser;
+
+#[test]
+fn tokens_cover_the_whole_source_without_building_an_ast() {
+    let mut parser = Parser::new("puts 1");
+    let tokens: Vec<_> = parser.tokens().collect();
+
+    assert!(!tokens.is_empty());
+    assert_eq!(tokens.first().unwrap().location.start, 0);
+    assert_eq!(tokens.last().unwrap().location.end, 6);
+}
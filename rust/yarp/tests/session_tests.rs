@@ -0,0 +1,39 @@
+use yarp::Parser;
+
+#[test]
+fn pretty_print_matches_old_ffi_behavior() {
+    let result = Parser::new("1 + 1").parse();
+    let printed = result.pretty_print();
+    assert!(printed.starts_with("ProgramNode"));
+}
+
+#[test]
+fn pretty_print_can_be_called_more_than_once() {
+    let result = Parser::new("class Foo; end").parse();
+    assert_eq!(result.pretty_print(), result.pretty_print());
+}
+
+#[test]
+fn source_is_preserved_verbatim() {
+    let source = b"puts 'hi'".to_vec();
+    let result = Parser::new(source.clone()).parse();
+    assert_eq!(result.source(), source.as_slice());
+}
+
+#[test]
+fn dropping_an_unparsed_parser_frees_it_without_panicking() {
+    drop(Parser::new("class Foo; end"));
+}
+
+#[test]
+fn dropping_a_parse_result_frees_node_then_parser_without_panicking() {
+    drop(Parser::new("class Foo; end").parse());
+}
+
+#[test]
+fn teardown_is_sound_after_a_partial_parse() {
+    // Mirrors `diagnostics_test`'s `class Foo;`: the parse fails partway
+    // through, leaving a populated error list. Destroying the node and
+    // freeing the parser still must not double-free or leak.
+    drop(Parser::new("class Foo;").parse());
+}